@@ -0,0 +1,142 @@
+/*
+ * © 2011-2017 Kornel Lesiński. All rights reserved.
+ *
+ * This file is part of DSSIM.
+ *
+ * DSSIM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License
+ * as published by the Free Software Foundation, either version 3
+ * of the License, or (at your option) any later version.
+ *
+ * DSSIM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the license along with DSSIM.
+ * If not, see <http://www.gnu.org/licenses/agpl.txt>.
+ */
+#![allow(non_snake_case)]
+
+use imgref::*;
+use std::mem::MaybeUninit;
+
+/// SSIM's reference window (Wang et al.) is an 11×11 Gaussian with σ≈1.5; that's the default
+/// here, but it's configurable via [`crate::Dssim::set_blur_sigma()`].
+pub(crate) const DEFAULT_SIGMA: f64 = 1.5;
+
+/// Young–van Vliet (1995) recursive coefficients: a forward and a backward 3rd-order IIR
+/// pass per dimension approximate a true Gaussian blur in O(n) time, independent of σ.
+struct Coeffs {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    b3: f64,
+    b: f64,
+}
+
+impl Coeffs {
+    fn new(sigma: f64) -> Self {
+        let q = if sigma >= 2.5 {
+            0.98711 * sigma - 0.96330
+        } else {
+            3.97156 - 4.14554 * (1.0 - 0.26891 * sigma).sqrt()
+        };
+
+        let b0 = 1.57825 + q * (2.44413 + q * (1.4281 + q * 0.422205));
+        let b1 = q * (2.44413 + q * (2.85619 + q * 1.26661));
+        let b2 = -q * q * (1.4281 + q * 1.26661);
+        let b3 = q * q * q * 0.422205;
+        let b = 1.0 - (b1 + b2 + b3) / b0;
+
+        Coeffs { b0, b1, b2, b3, b }
+    }
+
+    /// Forward pass, then backward pass of the same recurrence, over one row/column in place
+    fn filter_line(&self, line: &mut [f64]) {
+        let n = line.len();
+        if n == 0 {
+            return;
+        }
+
+        // Forward pass, boundary initialized from the first sample
+        let first = line[0];
+        let mut out = vec![0.0; n];
+        for i in 0..n {
+            let prev = |k: usize| if i >= k { out[i - k] } else { first };
+            out[i] = self.b * line[i] + (self.b1 * prev(1) + self.b2 * prev(2) + self.b3 * prev(3)) / self.b0;
+        }
+
+        // Backward pass over the forward result, boundary initialized from the last sample
+        let last = out[n - 1];
+        let mut back = vec![0.0; n];
+        for i in (0..n).rev() {
+            let next = |k: usize| if i + k < n { back[i + k] } else { last };
+            back[i] = self.b * out[i] + (self.b1 * next(1) + self.b2 * next(2) + self.b3 * next(3)) / self.b0;
+        }
+
+        line.copy_from_slice(&back);
+    }
+}
+
+fn blur_plane(width: usize, height: usize, buf: &mut [f64], sigma: f64) {
+    let coeffs = Coeffs::new(sigma);
+
+    for row in buf.chunks_exact_mut(width) {
+        coeffs.filter_line(row);
+    }
+
+    let mut col = vec![0.0; height];
+    for x in 0..width {
+        for (y, v) in col.iter_mut().enumerate() {
+            *v = buf[y * width + x];
+        }
+        coeffs.filter_line(&mut col);
+        for (y, &v) in col.iter().enumerate() {
+            buf[y * width + x] = v;
+        }
+    }
+}
+
+/// Blur `img` in place with a separable recursive Gaussian of the given σ
+pub(crate) fn blur_in_place(mut img: ImgRefMut<'_, f32>, _tmp: &mut [MaybeUninit<f32>], sigma: f64) {
+    let width = img.width();
+    let height = img.height();
+
+    let mut buf: Vec<f64> = img.rows().flat_map(|row| row[0..width].iter().map(|&v| f64::from(v))).collect();
+    blur_plane(width, height, &mut buf, sigma);
+
+    for (row, out_row) in img.rows_mut().zip(buf.chunks_exact(width)) {
+        for (px, &v) in row[0..width].iter_mut().zip(out_row) {
+            *px = v as f32;
+        }
+    }
+}
+
+/// Blur `img`, returning a new plane of the same size
+pub(crate) fn blur(img: ImgRef<'_, f32>, _tmp: &mut [MaybeUninit<f32>], sigma: f64) -> ImgVec<f32> {
+    let width = img.width();
+    let height = img.height();
+
+    let mut buf: Vec<f64> = img.rows().flat_map(|row| row[0..width].iter().map(|&v| f64::from(v))).collect();
+    blur_plane(width, height, &mut buf, sigma);
+
+    let out: Vec<f32> = buf.into_iter().map(|v| v as f32).collect();
+    ImgVec::new(out, width, height)
+}
+
+#[test]
+fn flat_plane_is_unchanged() {
+    let width = 32;
+    let height = 32;
+    let img = ImgVec::new(vec![0.5f32; width * height], width, height);
+
+    let mut tmp = Vec::with_capacity(width * height);
+    let tmp = &mut tmp.spare_capacity_mut()[..width * height];
+    let blurred = blur(img.as_ref(), tmp, DEFAULT_SIGMA);
+
+    for v in blurred.pixels() {
+        assert!(v.is_finite(), "blur produced a non-finite value: {v}");
+        assert!((v - 0.5).abs() < 0.001, "flat plane should stay flat, got {v}");
+    }
+}
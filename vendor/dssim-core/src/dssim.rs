@@ -22,7 +22,11 @@
 use crate::blur;
 use crate::image::*;
 use crate::linear::ToRGBAPLU;
+pub use crate::resample::ResizeFilter;
+use crate::resample::resample_plane;
+use crate::tolab::GBitmap;
 pub use crate::tolab::ToLABBitmap;
+pub use crate::tolab::ToXYBBitmap;
 pub use crate::val::Dssim as Val;
 use imgref::*;
 use itertools::multizip;
@@ -37,7 +41,7 @@ use std::ops::Deref;
 use std::sync::Arc;
 
 trait Channable<T, I> {
-    fn img1_img2_blur(&self, modified: &Self, tmp: &mut [MaybeUninit<I>]) -> Vec<T>;
+    fn img1_img2_blur(&self, modified: &Self, tmp: &mut [MaybeUninit<I>], sigma: f64) -> Vec<T>;
 }
 
 #[derive(Clone)]
@@ -55,6 +59,35 @@ struct DssimChan<T> {
 pub struct Dssim {
     scale_weights: Vec<f64>,
     save_maps_scales: u8,
+    color_space: ColorSpace,
+    resize_filter: Option<ResizeFilter>,
+    blur_sigma: f64,
+    pooling: Pooling,
+}
+
+/// How a scale's per-pixel SSIM map collapses to a single scalar before the weighted
+/// cross-scale sum in [`Dssim::compare()`]. Each variant describes an error measure of the
+/// map; the scale's score is 1 minus that measure. `1−s` is clamped at 0 for the
+/// `MinkowskiP` and `Percentile` measures.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pooling {
+    /// The default: mean absolute deviation around a power-adjusted average of the map.
+    Mean,
+    /// `(Σ (1−sᵢ)^p / N)^(1/p)` over the map; larger `p` weighs the worst local regions
+    /// more heavily than the mean does, which helps surface small, badly-degraded areas.
+    MinkowskiP(f64),
+    /// The `q`-th percentile (0..=100) of `1−s` across the map.
+    Percentile(f64),
+}
+
+/// Perceptual color space the comparison pyramid is built in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// CIE L\*a\*b\*, used by the classic multiscale SSIM/DSSIM score
+    Lab,
+    /// XYB, as used by JPEG XL; the recommended space for [`Dssim::ssimulacra2_features()`],
+    /// which does not check the color space its inputs were created in
+    Xyb,
 }
 
 #[derive(Clone)]
@@ -85,6 +118,27 @@ impl<T> DssimImage<T> {
 // Weighed scales are inspired by the IW-SSIM, but details of the algorithm and weights are different
 const DEFAULT_WEIGHTS: [f64; 5] = [0.028, 0.197, 0.322, 0.298, 0.155];
 
+/// Number of pyramid scales used by [`Dssim::ssimulacra2_features()`]
+const SSIMULACRA2_SCALES: usize = 6;
+/// Per scale/channel we pool 3 maps (SSIM, artifact, detail-loss) with 2 norms each
+const SSIMULACRA2_MAPS_PER_CHANNEL: usize = 3 * 2;
+/// Upper bound on the feature vector: 6 scales × 3 channels × 3 maps × 2 norms
+const SSIMULACRA2_MAX_FEATURES: usize = SSIMULACRA2_SCALES * 3 * SSIMULACRA2_MAPS_PER_CHANNEL;
+
+/// Arithmetic mean (1-norm) pooling of a per-pixel map
+fn pool_mean(map: &[f32]) -> f64 {
+    map.iter().fold(0.0, |sum, &v| sum + f64::from(v)) / map.len() as f64
+}
+
+/// 4-norm pooling: `(mean(x⁴))^(1/4)`, weighs the worst pixels more than the mean does
+fn pool_4norm(map: &[f32]) -> f64 {
+    let sum4 = map.iter().fold(0.0, |sum, &v| {
+        let v = f64::from(v);
+        sum + v * v * v * v
+    });
+    (sum4 / map.len() as f64).powf(0.25)
+}
+
 /// Detailed comparison result
 #[derive(Clone)]
 pub struct SsimMap {
@@ -116,7 +170,7 @@ impl DssimChan<f32> {
 }
 
 impl DssimChan<f32> {
-    fn preprocess(&mut self, tmp: &mut [MaybeUninit<f32>]) {
+    fn preprocess(&mut self, tmp: &mut [MaybeUninit<f32>], sigma: f64) {
         let width = self.width;
         let height = self.height;
         assert!(width > 0);
@@ -127,24 +181,24 @@ impl DssimChan<f32> {
         debug_assert!(img.pixels().all(|i| i.is_finite()));
 
         if self.is_chroma {
-            blur::blur_in_place(img.as_mut(), tmp);
+            blur::blur_in_place(img.as_mut(), tmp, sigma);
         }
-        let (mu, _, _) = blur::blur(img.as_ref(), tmp).into_contiguous_buf();
+        let (mu, _, _) = blur::blur(img.as_ref(), tmp, sigma).into_contiguous_buf();
         self.mu = mu;
 
         self.img_sq_blur = img.pixels().map(|i| {
             debug_assert!(i <= 1.0 && i >= 0.0);
             i * i
         }).collect();
-        blur::blur_in_place(ImgRefMut::new(&mut self.img_sq_blur[..], width, height), tmp);
+        blur::blur_in_place(ImgRefMut::new(&mut self.img_sq_blur[..], width, height), tmp, sigma);
     }
 }
 
 impl Channable<LAB, f32> for [DssimChan<f32>] {
-    fn img1_img2_blur(&self, modified: &Self, tmp32: &mut [MaybeUninit<f32>]) -> Vec<LAB> {
+    fn img1_img2_blur(&self, modified: &Self, tmp32: &mut [MaybeUninit<f32>], sigma: f64) -> Vec<LAB> {
 
         let blurred:Vec<_> = self.iter().zip(modified.iter()).map(|(o,m)|{
-            o.img1_img2_blur(m, tmp32)
+            o.img1_img2_blur(m, tmp32, sigma)
         }).collect();
 
         return multizip((blurred[0].iter().copied(), blurred[1].iter().copied(), blurred[2].iter().copied())).map(|(l,a,b)| {
@@ -154,7 +208,7 @@ impl Channable<LAB, f32> for [DssimChan<f32>] {
 }
 
 impl Channable<f32, f32> for DssimChan<f32> {
-    fn img1_img2_blur(&self, modified: &Self, tmp32: &mut [MaybeUninit<f32>]) -> Vec<f32> {
+    fn img1_img2_blur(&self, modified: &Self, tmp32: &mut [MaybeUninit<f32>], sigma: f64) -> Vec<f32> {
         let modified_img = modified.img.as_ref().unwrap();
         let width = modified_img.width();
         let height = modified_img.height();
@@ -174,7 +228,7 @@ impl Channable<f32, f32> for DssimChan<f32> {
         }
 
         debug_assert_eq!(out.len(), width * height);
-        blur::blur_in_place(ImgRefMut::new(&mut out, width, height), tmp32);
+        blur::blur_in_place(ImgRefMut::new(&mut out, width, height), tmp32, sigma);
         out
     }
 }
@@ -186,6 +240,10 @@ impl Dssim {
         Dssim {
             scale_weights: DEFAULT_WEIGHTS[..].to_owned(),
             save_maps_scales: 0,
+            color_space: ColorSpace::Lab,
+            resize_filter: None,
+            blur_sigma: blur::DEFAULT_SIGMA,
+            pooling: Pooling::Mean,
         }
     }
 
@@ -199,6 +257,34 @@ impl Dssim {
         self.save_maps_scales = num_scales;
     }
 
+    /// Choose the perceptual color space `create_image()` converts pixels into.
+    ///
+    /// Defaults to `ColorSpace::Lab`. Switch to `ColorSpace::Xyb` before calling
+    /// `create_image()` if you intend to use `ssimulacra2_features()`.
+    pub fn set_color_space(&mut self, color_space: ColorSpace) {
+        self.color_space = color_space;
+    }
+
+    /// Pick the resampling filter used to build the scale pyramid.
+    ///
+    /// Left as `None` by default, which keeps the cheap `Downsample`-trait halving. Set this
+    /// to reduce aliasing in the downscaled scales, at the cost of a slower pyramid build.
+    pub fn set_resize_filter(&mut self, resize_filter: Option<ResizeFilter>) {
+        self.resize_filter = resize_filter;
+    }
+
+    /// Set the σ of the Gaussian window used to blur channels before comparing them.
+    ///
+    /// Defaults to 1.5, matching the 11×11 window from Wang et al.'s reference SSIM.
+    pub fn set_blur_sigma(&mut self, sigma: f64) {
+        self.blur_sigma = sigma;
+    }
+
+    /// Set how each scale's SSIM map is pooled into a scalar score. Defaults to `Pooling::Mean`.
+    pub fn set_pooling(&mut self, pooling: Pooling) {
+        self.pooling = pooling;
+    }
+
     /// Create image from an array of RGBA pixels (sRGB, non-premultiplied, alpha last).
     ///
     /// If you have a slice of `u8`, then see `rgb` crate's `as_rgba()`.
@@ -233,38 +319,80 @@ impl Dssim {
     /// You can implement `ToLABBitmap` and `Downsample` traits on your own image type.
     pub fn create_image<InBitmap, OutBitmap>(&self, src_img: &InBitmap) -> Option<DssimImage<f32>>
     where
-        InBitmap: ToLABBitmap + Send + Sync + Downsample<Output = OutBitmap>,
-        OutBitmap: ToLABBitmap + Send + Sync + Downsample<Output = OutBitmap>,
+        InBitmap: ToLABBitmap + ToXYBBitmap + Send + Sync + Downsample<Output = OutBitmap>,
+        OutBitmap: ToLABBitmap + ToXYBBitmap + Send + Sync + Downsample<Output = OutBitmap>,
     {
         let num_scales = self.scale_weights.len();
         let mut scale = Vec::with_capacity(num_scales);
-        Self::make_scales_recursive(num_scales, MaybeArc::Borrowed(src_img), &mut scale);
+        match self.resize_filter {
+            None => Self::make_scales_recursive(num_scales, self.color_space, self.blur_sigma, MaybeArc::Borrowed(src_img), &mut scale),
+            Some(filter) => {
+                let planes = match self.color_space {
+                    ColorSpace::Lab => src_img.to_lab(),
+                    ColorSpace::Xyb => src_img.to_xyb(),
+                };
+                Self::make_scales_from_planes(num_scales, filter, self.blur_sigma, planes, &mut scale);
+            },
+        }
         scale.reverse(); // depth-first made smallest scales first
 
         Some(DssimImage { scale })
     }
 
+    /// Alternative to `make_scales_recursive()` for when a high-quality `ResizeFilter` is
+    /// configured: convert to perceptual planes once, then downsample *those* planes for
+    /// each coarser scale instead of re-converting a downsampled copy of the source pixels.
+    fn make_scales_from_planes(scales_left: usize, filter: ResizeFilter, sigma: f64, planes: Vec<GBitmap>, scales: &mut Vec<DssimChanScale<f32>>) {
+        let chan = DssimChanScale {
+            chan: planes.iter().enumerate().map(|(n, l)| {
+                let w = l.width();
+                let h = l.height();
+                let mut ch = DssimChan::new(l.clone(), n > 0);
+
+                let pixels = w * h;
+                let mut tmp = Vec::with_capacity(pixels);
+                ch.preprocess(&mut tmp.spare_capacity_mut()[..pixels], sigma);
+                ch
+            }).collect(),
+        };
+
+        if scales_left > 0 {
+            let width = planes[0].width();
+            let height = planes[0].height();
+            if width > 1 && height > 1 {
+                let new_width = (width + 1) / 2;
+                let new_height = (height + 1) / 2;
+                let downsampled: Vec<GBitmap> = planes.iter().map(|p| resample_plane(p.as_ref(), new_width, new_height, filter)).collect();
+                Self::make_scales_from_planes(scales_left - 1, filter, sigma, downsampled, scales);
+            }
+        }
+        scales.push(chan);
+    }
+
     #[inline(never)]
-    fn make_scales_recursive<InBitmap, OutBitmap>(scales_left: usize, image: MaybeArc<'_, InBitmap>, scales: &mut Vec<DssimChanScale<f32>>)
+    fn make_scales_recursive<InBitmap, OutBitmap>(scales_left: usize, color_space: ColorSpace, sigma: f64, image: MaybeArc<'_, InBitmap>, scales: &mut Vec<DssimChanScale<f32>>)
     where
-        InBitmap: ToLABBitmap + Send + Sync + Downsample<Output = OutBitmap>,
-        OutBitmap: ToLABBitmap + Send + Sync + Downsample<Output = OutBitmap>,
+        InBitmap: ToLABBitmap + ToXYBBitmap + Send + Sync + Downsample<Output = OutBitmap>,
+        OutBitmap: ToLABBitmap + ToXYBBitmap + Send + Sync + Downsample<Output = OutBitmap>,
     {
-        // Run to_lab and next downsampling in parallel
+        // Run the color conversion and next downsampling in parallel
         let (chan, _) = rayon::join({
             let image = image.clone();
             move || {
-                let lab = image.to_lab();
+                let planes = match color_space {
+                    ColorSpace::Lab => image.to_lab(),
+                    ColorSpace::Xyb => image.to_xyb(),
+                };
                 drop(image); // Free larger RGB image ASAP
                 DssimChanScale {
-                    chan: lab.into_par_iter().enumerate().map(|(n,l)| {
+                    chan: planes.into_par_iter().enumerate().map(|(n,l)| {
                         let w = l.width();
                         let h = l.height();
                         let mut ch = DssimChan::new(l, n > 0);
 
                         let pixels = w * h;
                         let mut tmp = Vec::with_capacity(pixels);
-                        ch.preprocess(&mut tmp.spare_capacity_mut()[..pixels]);
+                        ch.preprocess(&mut tmp.spare_capacity_mut()[..pixels], sigma);
                         ch
                     }).collect(),
                 }
@@ -276,7 +404,7 @@ impl Dssim {
                     let down = image.downsample();
                     drop(image);
                     if let Some(downsampled) = down {
-                        Self::make_scales_recursive(scales_left - 1, MaybeArc::Owned(Arc::new(downsampled)), scales);
+                        Self::make_scales_recursive(scales_left - 1, color_space, sigma, MaybeArc::Owned(Arc::new(downsampled)), scales);
                     }
                 }
             }
@@ -306,23 +434,37 @@ impl Dssim {
                     let (original_lab, (img1_img2_blur, modified_lab)) = rayon::join(
                     || Self::lab_chan(original_image_scale),
                     || {
-                        let img1_img2_blur = original_image_scale.chan.img1_img2_blur(&modified_image_scale.chan, tmp);
+                        let img1_img2_blur = original_image_scale.chan.img1_img2_blur(&modified_image_scale.chan, tmp, self.blur_sigma);
                         (img1_img2_blur, Self::lab_chan(modified_image_scale))
                     });
 
                     Self::compare_scale(&original_lab, &modified_lab, &img1_img2_blur)
                 },
                 1 => {
-                    let img1_img2_blur = original_image_scale.chan[0].img1_img2_blur(&modified_image_scale.chan[0], tmp);
+                    let img1_img2_blur = original_image_scale.chan[0].img1_img2_blur(&modified_image_scale.chan[0], tmp, self.blur_sigma);
                     Self::compare_scale(&original_image_scale.chan[0], &modified_image_scale.chan[0], &img1_img2_blur)
                 },
                 _ => panic!(),
             };
 
-            let sum = ssim_map.pixels().fold(0., |sum, i| sum + f64::from(i));
             let len = (ssim_map.width()*ssim_map.height()) as f64;
-            let avg = (sum / len).max(0.0).powf((0.5_f64).powf(n as f64));
-            let score = 1.0 - (ssim_map.pixels().fold(0., |sum, i| sum + (avg - f64::from(i)).abs()) / len);
+            let score = match self.pooling {
+                Pooling::Mean => {
+                    let sum = ssim_map.pixels().fold(0., |sum, i| sum + f64::from(i));
+                    let avg = (sum / len).max(0.0).powf((0.5_f64).powf(n as f64));
+                    1.0 - (ssim_map.pixels().fold(0., |sum, i| sum + (avg - f64::from(i)).abs()) / len)
+                },
+                Pooling::MinkowskiP(p) => {
+                    let sum = ssim_map.pixels().fold(0.0, |sum, i| sum + (1.0 - f64::from(i)).max(0.0).powf(p));
+                    1.0 - (sum / len).powf(1.0 / p)
+                },
+                Pooling::Percentile(q) => {
+                    let mut errors: Vec<f64> = ssim_map.pixels().map(|i| (1.0 - f64::from(i)).max(0.0)).collect();
+                    errors.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                    let idx = (((q / 100.0) * (errors.len() - 1) as f64).round() as usize).min(errors.len() - 1);
+                    1.0 - errors[idx]
+                },
+            };
 
             let map = if self.save_maps_scales as usize > n {
                 Some(SsimMap {
@@ -349,6 +491,94 @@ impl Dssim {
         (to_dssim(ssim_sum / weight_sum).into(), ssim_maps)
     }
 
+    /// Compute the raw SSIMULACRA2-style feature vector for a comparison, without combining
+    /// it into a single score.
+    ///
+    /// There's no `compare_ssimulacra2()` returning a calibrated scalar: that needs a weight
+    /// vector and bias fitted by regression against a corpus of human-rated images, the way
+    /// the real SSIMULACRA2 (libjxl's `ssimulacra2.cc`) was, and nobody has done that fit
+    /// here. Shipping made-up numbers under a "100 ≈ perfect" claim would make every score
+    /// meaningless rather than merely imprecise, so this returns the unweighted features
+    /// instead — combine them yourself once you have (or bring your own) trained weights.
+    ///
+    /// Features are laid out scale-by-scale (full resolution first, matching the pyramid order),
+    /// each channel of each scale contributing 6 values in turn (ssim, artifact,
+    /// detail-loss, each pooled by mean then 4-norm, all error-like: 0 is a perfect match,
+    /// larger is worse); a 3-channel scale is 18 consecutive entries, a grayscale one is 6.
+    /// Both images must have been created with enough scales (see `set_scales()`) to cover
+    /// the 6 scales this pools over; any scales beyond that are ignored. For best results,
+    /// create both images with `set_color_space(ColorSpace::Xyb)` first.
+    #[inline(never)]
+    pub fn ssimulacra2_features<M: Borrow<DssimImage<f32>>>(&self, original_image: &DssimImage<f32>, modified_image: M) -> Vec<f64> {
+        let modified_image = modified_image.borrow();
+
+        original_image.scale.iter().zip(modified_image.scale.iter())
+            .take(SSIMULACRA2_SCALES)
+            .flat_map(|(original_scale, modified_scale)| {
+                let scale_width = original_scale.chan[0].width;
+                let scale_height = original_scale.chan[0].height;
+
+                // One 6-feature block per channel, so a 3-channel scale contributes 18
+                // features and a grayscale one contributes 6 — never a merged channel.
+                original_scale.chan.iter().zip(modified_scale.chan.iter()).flat_map(move |(original_chan, modified_chan)| {
+                    let mut tmp = Vec::with_capacity(scale_width * scale_height);
+                    let tmp = &mut tmp.spare_capacity_mut()[0..scale_width * scale_height];
+                    let img1_img2_blur = original_chan.img1_img2_blur(modified_chan, tmp, self.blur_sigma);
+                    Self::compare_scale_ssimulacra2(original_chan, modified_chan, &img1_img2_blur)
+                }).collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Per scale/channel: the usual SSIM map, plus two asymmetric error maps derived from the
+    /// same blurred second-moment terms — `artifact` is variance in the modified image that
+    /// isn't explained by correlation with the original (structure that wasn't there before),
+    /// and `detail_loss` is the mirror image of that (structure the original had that's gone).
+    /// Each of the 3 maps is pooled with the mean (1-norm) and the 4-norm, in that order.
+    fn compare_scale_ssimulacra2<L>(original: &DssimChan<L>, modified: &DssimChan<L>, img1_img2_blur: &[L]) -> [f64; SSIMULACRA2_MAPS_PER_CHANNEL]
+    where
+        L: Send + Sync + Clone + Copy + ops::Mul<Output = L> + ops::Sub<Output = L> + 'static,
+        f32: From<L>,
+    {
+        assert_eq!(original.width, modified.width);
+        assert_eq!(original.height, modified.height);
+
+        let c1 = 0.01 * 0.01;
+        let c2 = 0.03 * 0.03;
+
+        let mut ssim_map = Vec::with_capacity(img1_img2_blur.len());
+        let mut artifact_map = Vec::with_capacity(img1_img2_blur.len());
+        let mut detail_loss_map = Vec::with_capacity(img1_img2_blur.len());
+
+        for (((&img1_img2_blur, &mu1), &mu2), (&img1_sq_blur, &img2_sq_blur)) in img1_img2_blur.iter()
+            .zip(original.mu.iter()).zip(modified.mu.iter())
+            .zip(original.img_sq_blur.iter().zip(modified.img_sq_blur.iter()))
+        {
+            let mu1mu1 = mu1 * mu1;
+            let mu1mu2 = mu1 * mu2;
+            let mu2mu2 = mu2 * mu2;
+            let mu1_sq: f32 = mu1mu1.into();
+            let mu2_sq: f32 = mu2mu2.into();
+            let mu1_mu2: f32 = mu1mu2.into();
+            let sigma1_sq: f32 = (img1_sq_blur - mu1mu1).into();
+            let sigma2_sq: f32 = (img2_sq_blur - mu2mu2).into();
+            let sigma12: f32 = (img1_img2_blur - mu1mu2).into();
+
+            let s = 2.0f32.mul_add(mu1_mu2, c1) * 2.0f32.mul_add(sigma12, c2) /
+                       ((mu1_sq + mu2_sq + c1) * (sigma1_sq + sigma2_sq + c2));
+
+            ssim_map.push(1.0 - s);
+            artifact_map.push((sigma2_sq - sigma12).max(0.0));
+            detail_loss_map.push((sigma1_sq - sigma12).max(0.0));
+        }
+
+        [
+            pool_mean(&ssim_map), pool_4norm(&ssim_map),
+            pool_mean(&artifact_map), pool_4norm(&artifact_map),
+            pool_mean(&detail_loss_map), pool_4norm(&detail_loss_map),
+        ]
+    }
+
     fn lab_chan(scale: &DssimChanScale<f32>) -> DssimChan<LAB> {
         let l = &scale.chan[0];
         let a = &scale.chan[1];
@@ -418,6 +648,34 @@ fn to_dssim(ssim: f64) -> f64 {
     1.0 / ssim.max(std::f64::EPSILON) - 1.0
 }
 
+#[test]
+fn ssimulacra2_runs_and_is_finite() {
+    use rgb::RGBA;
+
+    let mut d = new();
+    d.set_scales(&[1.0; SSIMULACRA2_SCALES]);
+
+    let width = 256;
+    let height = 256;
+    let buf1: Vec<RGBA<u8>> = vec![RGBA::new(255, 0, 0, 255); width * height];
+    let buf2: Vec<RGBA<u8>> = vec![RGBA::new(250, 0, 0, 255); width * height];
+    let img1 = d.create_image_rgba(&buf1, width, height).unwrap();
+    let img2 = d.create_image_rgba(&buf2, width, height).unwrap();
+
+    // 6 scales × 3 color channels × 6 pooled values
+    let features = d.ssimulacra2_features(&img1, &img2);
+    assert_eq!(features.len(), SSIMULACRA2_MAX_FEATURES);
+    assert!(features.iter().all(|f| f.is_finite()));
+
+    // Features are error-like (0 = perfect match), so identical images should pool to ~0
+    // everywhere, and never above the near-identical pair.
+    let identical = d.ssimulacra2_features(&img1, &img1);
+    for (&a, &b) in identical.iter().zip(features.iter()) {
+        assert!(a.abs() < 1e-6, "identical images should have ~0 error, got {a}");
+        assert!(a <= b, "identical images shouldn't score worse than a near-identical pair");
+    }
+}
+
 #[test]
 fn png_compare() {
     use crate::linear::*;
@@ -503,3 +761,16 @@ fn poison() {
     let (res, _) = d.compare(&sub_img1, sub_img2);
     assert!(res < 0.000001);
 }
+
+#[test]
+fn ssimulacra2_pooling() {
+    let uniform = [0.8f32; 16];
+    assert!((pool_mean(&uniform) - 0.8).abs() < 1e-6);
+    assert!((pool_4norm(&uniform) - 0.8).abs() < 1e-6);
+
+    // These maps are error-like (0 = no error), so a rare bad pixel should dominate the
+    // 4-norm far more than it dominates a plain mean.
+    let mut mostly_good = vec![0.0f32; 15];
+    mostly_good.push(1.0);
+    assert!(pool_4norm(&mostly_good) > pool_mean(&mostly_good));
+}
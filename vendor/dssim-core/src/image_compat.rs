@@ -0,0 +1,198 @@
+/*
+ * © 2011-2017 Kornel Lesiński. All rights reserved.
+ *
+ * This file is part of DSSIM.
+ *
+ * DSSIM is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License
+ * as published by the Free Software Foundation, either version 3
+ * of the License, or (at your option) any later version.
+ *
+ * DSSIM is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the license along with DSSIM.
+ * If not, see <http://www.gnu.org/licenses/agpl.txt>.
+ */
+//! Conversions from the `image` crate's `DynamicImage`/`ImageBuffer` to [`DssimImage`],
+//! enabled with the `image` cargo feature. Saves callers who already decode with `image`
+//! from hand-converting to `rgb`/`imgref` types themselves.
+#![cfg(feature = "image")]
+
+use crate::dssim::{Dssim, DssimImage};
+use crate::linear::ToRGBAPLU;
+use image::{DynamicImage, GenericImageView, ImageBuffer, Luma, Rgb, Rgba};
+use imgref::ImgVec;
+use rgb::{RGB, RGBA};
+
+impl Dssim {
+    /// Create a comparable image directly from an `image` crate `DynamicImage`.
+    ///
+    /// Handles any of `DynamicImage`'s pixel layouts and bit depths: `ImageRgba16`,
+    /// `ImageRgb16` and `ImageLuma16` are routed through the full-precision `ToDssimImage`
+    /// impls below instead of being downconverted; everything else (including `ImageLumaA16`
+    /// and the float variants) goes through `to_rgba8()` then `create_image_rgba()`.
+    #[must_use]
+    pub fn create_image_from_dynamic(&self, img: &DynamicImage) -> Option<DssimImage<f32>> {
+        match img {
+            DynamicImage::ImageRgba16(buf) => buf.to_dssim_image(self),
+            DynamicImage::ImageRgb16(buf) => buf.to_dssim_image(self),
+            DynamicImage::ImageLuma16(buf) => buf.to_dssim_image(self),
+            _ => {
+                let width = img.width() as usize;
+                let height = img.height() as usize;
+                let rgba8 = img.to_rgba8();
+                let pixels: Vec<RGBA<u8>> = rgba8.pixels().map(|p| {
+                    let [r, g, b, a] = p.0;
+                    RGBA::new(r, g, b, a)
+                }).collect();
+                self.create_image_rgba(&pixels, width, height)
+            },
+        }
+    }
+}
+
+/// Implemented for the `image` crate's buffer types, so `to_dssim_image()` turns them into a
+/// [`DssimImage`] without the caller converting to `rgb`/`imgref` types for
+/// [`Dssim::create_image()`][create_image] first.
+///
+/// [create_image]: crate::Dssim::create_image
+pub trait ToDssimImage {
+    fn to_dssim_image(&self, dssim: &Dssim) -> Option<DssimImage<f32>>;
+}
+
+impl ToDssimImage for ImageBuffer<Rgba<u8>, Vec<u8>> {
+    fn to_dssim_image(&self, dssim: &Dssim) -> Option<DssimImage<f32>> {
+        let pixels: Vec<RGBA<u8>> = self.pixels().map(|p| {
+            let [r, g, b, a] = p.0;
+            RGBA::new(r, g, b, a)
+        }).collect();
+        dssim.create_image_rgba(&pixels, self.width() as usize, self.height() as usize)
+    }
+}
+
+impl ToDssimImage for ImageBuffer<Rgb<u8>, Vec<u8>> {
+    fn to_dssim_image(&self, dssim: &Dssim) -> Option<DssimImage<f32>> {
+        let pixels: Vec<RGB<u8>> = self.pixels().map(|p| {
+            let [r, g, b] = p.0;
+            RGB::new(r, g, b)
+        }).collect();
+        dssim.create_image_rgb(&pixels, self.width() as usize, self.height() as usize)
+    }
+}
+
+impl ToDssimImage for ImageBuffer<Luma<u8>, Vec<u8>> {
+    fn to_dssim_image(&self, dssim: &Dssim) -> Option<DssimImage<f32>> {
+        // Treat gray as R=G=B so it goes through the same sRGB->linear decode as every
+        // other path here, instead of dividing the raw byte as if it were already linear.
+        let pixels: Vec<RGB<u8>> = self.pixels().map(|p| {
+            let g = p.0[0];
+            RGB::new(g, g, g)
+        }).collect();
+        dssim.create_image_rgb(&pixels, self.width() as usize, self.height() as usize)
+    }
+}
+
+impl ToDssimImage for ImageBuffer<Rgba<u16>, Vec<u16>> {
+    fn to_dssim_image(&self, dssim: &Dssim) -> Option<DssimImage<f32>> {
+        let pixels: Vec<RGBA<u16>> = self.pixels().map(|p| {
+            let [r, g, b, a] = p.0;
+            RGBA::new(r, g, b, a)
+        }).collect();
+        let img = ImgVec::new(pixels.to_rgbaplu(), self.width() as usize, self.height() as usize);
+        dssim.create_image(&img)
+    }
+}
+
+impl ToDssimImage for ImageBuffer<Rgb<u16>, Vec<u16>> {
+    fn to_dssim_image(&self, dssim: &Dssim) -> Option<DssimImage<f32>> {
+        let pixels: Vec<RGB<u16>> = self.pixels().map(|p| {
+            let [r, g, b] = p.0;
+            RGB::new(r, g, b)
+        }).collect();
+        let img = ImgVec::new(pixels.to_rgblu(), self.width() as usize, self.height() as usize);
+        dssim.create_image(&img)
+    }
+}
+
+impl ToDssimImage for ImageBuffer<Luma<u16>, Vec<u16>> {
+    fn to_dssim_image(&self, dssim: &Dssim) -> Option<DssimImage<f32>> {
+        // Treat gray as R=G=B so it goes through the same sRGB->linear decode as every
+        // other path here, instead of dividing the raw sample as if it were already linear.
+        let pixels: Vec<RGB<u16>> = self.pixels().map(|p| {
+            let g = p.0[0];
+            RGB::new(g, g, g)
+        }).collect();
+        let img = ImgVec::new(pixels.to_rgblu(), self.width() as usize, self.height() as usize);
+        dssim.create_image(&img)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_to_dssim_image_impl_self_compares_to_zero() {
+        let d = Dssim::new();
+        let width = 8;
+        let height = 8;
+
+        macro_rules! assert_self_compare_is_zero {
+            ($buf:expr) => {
+                let img = $buf.to_dssim_image(&d).unwrap();
+                let (score, _) = d.compare(&img, img.clone());
+                assert!(score < 0.000001, "{}: self-compare should be ~0, got {score}", stringify!($buf));
+            };
+        }
+
+        assert_self_compare_is_zero!(ImageBuffer::<Rgba<u8>, Vec<u8>>::from_pixel(width, height, Rgba([200, 100, 50, 255])));
+        assert_self_compare_is_zero!(ImageBuffer::<Rgb<u8>, Vec<u8>>::from_pixel(width, height, Rgb([200, 100, 50])));
+        assert_self_compare_is_zero!(ImageBuffer::<Luma<u8>, Vec<u8>>::from_pixel(width, height, Luma([128])));
+        assert_self_compare_is_zero!(ImageBuffer::<Rgba<u16>, Vec<u16>>::from_pixel(width, height, Rgba([51200, 25600, 12800, 65535])));
+        assert_self_compare_is_zero!(ImageBuffer::<Rgb<u16>, Vec<u16>>::from_pixel(width, height, Rgb([51200, 25600, 12800])));
+        assert_self_compare_is_zero!(ImageBuffer::<Luma<u16>, Vec<u16>>::from_pixel(width, height, Luma([32768])));
+    }
+
+    #[test]
+    fn grayscale_matches_equivalent_color_luminance() {
+        // The bug this guards against: Luma<u8>/<u16> used to skip the sRGB->linear decode
+        // that every color path goes through, so a gray image scored differently from a
+        // color image carrying the same per-channel value.
+        let d = Dssim::new();
+        let width = 8;
+        let height = 8;
+
+        let gray8 = ImageBuffer::<Luma<u8>, Vec<u8>>::from_pixel(width, height, Luma([128]));
+        let color8 = ImageBuffer::<Rgb<u8>, Vec<u8>>::from_pixel(width, height, Rgb([128, 128, 128]));
+        let gray8_img = gray8.to_dssim_image(&d).unwrap();
+        let color8_img = color8.to_dssim_image(&d).unwrap();
+        let (score, _) = d.compare(&gray8_img, color8_img);
+        assert!(score < 0.000001, "gray8 vs equivalent color8 should match, got {score}");
+
+        let gray16 = ImageBuffer::<Luma<u16>, Vec<u16>>::from_pixel(width, height, Luma([32768]));
+        let color16 = ImageBuffer::<Rgb<u16>, Vec<u16>>::from_pixel(width, height, Rgb([32768, 32768, 32768]));
+        let gray16_img = gray16.to_dssim_image(&d).unwrap();
+        let color16_img = color16.to_dssim_image(&d).unwrap();
+        let (score, _) = d.compare(&gray16_img, color16_img);
+        assert!(score < 0.000001, "gray16 vs equivalent color16 should match, got {score}");
+    }
+
+    #[test]
+    fn dynamic_image_16bit_matches_direct_buffer_path() {
+        // The bug this guards against: create_image_from_dynamic always downconverted to
+        // 8 bits via to_rgba8(), so a 16-bit DynamicImage scored differently from feeding
+        // the same ImageBuffer through its direct ToDssimImage impl.
+        let d = Dssim::new();
+        let width = 8;
+        let height = 8;
+
+        let buf = ImageBuffer::<Rgb<u16>, Vec<u16>>::from_pixel(width, height, Rgb([51200, 25600, 12800]));
+        let via_dynamic = d.create_image_from_dynamic(&DynamicImage::ImageRgb16(buf.clone())).unwrap();
+        let direct = buf.to_dssim_image(&d).unwrap();
+        let (score, _) = d.compare(&via_dynamic, direct);
+        assert!(score < 0.000001, "16-bit DynamicImage should match the direct buffer path, got {score}");
+    }
+}
@@ -152,3 +152,144 @@ impl<'a> ToLABBitmap for ImgRef<'a, RGBLU> {
         })
     }
 }
+
+// XYB as used by JPEG XL (the "opsin" color space): a cone-response space that's generally
+// more perceptually uniform than L*a*b* for the kind of local-structure differences SSIM looks at.
+const OPSIN_BIAS: f32 = 0.0037930732552754493;
+
+#[inline(always)]
+fn opsin_cbrt(v: f32) -> f32 {
+    (v + OPSIN_BIAS).cbrt() - OPSIN_BIAS.cbrt()
+}
+
+pub(crate) trait ToXYB {
+    fn to_xyb(&self) -> (f32, f32, f32);
+}
+
+impl ToXYB for RGBLU {
+    /// Returns `(Y, X, B)`, luma first, mirroring `ToLAB`'s `(L, a, b)` order: plane 0 is
+    /// always the non-chroma channel so callers that special-case plane 0 (extra blurring
+    /// on the others, not this one) do the right thing regardless of color space.
+    fn to_xyb(&self) -> (f32, f32, f32) {
+        let l = fma_matrix(self.r, 0.30, self.g, 0.622, self.b, 0.078);
+        let m = fma_matrix(self.r, 0.23, self.g, 0.692, self.b, 0.078);
+        let s = fma_matrix(self.r, 0.243_422_69, self.g, 0.204_767_44, self.b, 0.551_809_87);
+
+        let l = opsin_cbrt(l);
+        let m = opsin_cbrt(m);
+        let s = opsin_cbrt(s);
+
+        // X = (l - m) / 2 is routinely negative (e.g. pure green pushes l below m); bias it
+        // into [0, 1] the same way ToLAB fudges a*/b* into non-negative range.
+        let xyb = ((l + m) / 2.0, (0.5f32).mul_add(l - m, 0.5), s);
+        debug_assert!(xyb.0.is_finite() && xyb.1.is_finite() && xyb.2.is_finite());
+        xyb
+    }
+}
+
+/// Convert image to XYB planar, the way `ToLABBitmap` converts to L\*a\*b\* planar
+///
+/// It should return 1 (gray) or 3 (color) planes.
+pub trait ToXYBBitmap {
+    fn to_xyb(&self) -> Vec<GBitmap>;
+}
+
+impl ToXYBBitmap for ImgVec<RGBAPLU> {
+    #[inline(always)]
+    fn to_xyb(&self) -> Vec<GBitmap> {
+        self.as_ref().to_xyb()
+    }
+}
+
+impl ToXYBBitmap for ImgVec<RGBLU> {
+    #[inline(always)]
+    fn to_xyb(&self) -> Vec<GBitmap> {
+        self.as_ref().to_xyb()
+    }
+}
+
+impl ToXYBBitmap for GBitmap {
+    #[inline(never)]
+    fn to_xyb(&self) -> Vec<GBitmap> {
+        // Grayscale has no chroma to separate out; return the single lightness plane of
+        // `to_lab()`, which the pyramid/compare path handles as a 1-plane image.
+        ToLABBitmap::to_lab(self)
+    }
+}
+
+#[inline(never)]
+fn rgb_to_xyb<T: Copy + Sync + Send + 'static, F>(img: ImgRef<'_, T>, cb: F) -> Vec<GBitmap>
+    where F: Fn(T, usize) -> (f32, f32, f32) + Sync + Send + 'static
+{
+    let width = img.width();
+    assert!(width > 0);
+    let height = img.height();
+    let area = width * height;
+
+    let mut out_y = Vec::with_capacity(area);
+    let mut out_x = Vec::with_capacity(area);
+    let mut out_b = Vec::with_capacity(area);
+
+    // For output width == stride
+    out_y.spare_capacity_mut().par_chunks_exact_mut(width).take(height).zip(
+        out_x.spare_capacity_mut().par_chunks_exact_mut(width).take(height).zip(
+            out_b.spare_capacity_mut().par_chunks_exact_mut(width).take(height))
+    ).enumerate()
+    .for_each(|(y, (y_row, (x_row, b_row)))| {
+        let in_row = &img.rows().nth(y).unwrap()[0..width];
+        let y_row = &mut y_row[0..width];
+        let x_row = &mut x_row[0..width];
+        let b_row = &mut b_row[0..width];
+        for i in 0..width {
+            let n = (i+11) ^ (y+11);
+            let (y_val, x_val, b) = cb(in_row[i], n);
+            y_row[i].write(y_val);
+            x_row[i].write(x_val);
+            b_row[i].write(b);
+        }
+    });
+
+    unsafe { out_y.set_len(area) };
+    unsafe { out_x.set_len(area) };
+    unsafe { out_b.set_len(area) };
+
+    vec![
+        Img::new(out_y, width, height),
+        Img::new(out_x, width, height),
+        Img::new(out_b, width, height),
+    ]
+}
+
+impl<'a> ToXYBBitmap for ImgRef<'a, RGBAPLU> {
+    #[inline]
+    fn to_xyb(&self) -> Vec<GBitmap> {
+        rgb_to_xyb(*self, |px, n|{
+            px.to_rgb(n).to_xyb()
+        })
+    }
+}
+
+impl<'a> ToXYBBitmap for ImgRef<'a, RGBLU> {
+    #[inline]
+    fn to_xyb(&self) -> Vec<GBitmap> {
+        rgb_to_xyb(*self, |px, _n|{
+            px.to_xyb()
+        })
+    }
+}
+
+#[test]
+fn xyb_plane_0_is_luma_and_in_range() {
+    // Pure green is the only primary with l below m, so it's the case that pushes the
+    // unbiased X negative if the bias ever regresses.
+    let green = RGBLU::new(0.0, 1.0, 0.0);
+    let (y, x, b) = green.to_xyb();
+    assert!((0.0..=1.0).contains(&y), "Y out of range: {y}");
+    assert!((0.0..=1.0).contains(&x), "X out of range: {x}");
+    assert!(b.is_finite());
+
+    // Plane 0 should be luma: white should be brighter on plane 0 than black.
+    let white = RGBLU::new(1.0, 1.0, 1.0).to_xyb();
+    let black = RGBLU::new(0.0, 0.0, 0.0).to_xyb();
+    assert!(white.0 > black.0, "plane 0 should track luma, not chroma");
+}